@@ -1,6 +1,11 @@
-use tauri::command;
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::json;
+use tauri::{command, AppHandle, Emitter};
+
+use crate::commands::remote::{ConnectionRegistry, RemoteRequest, RemoteResponse};
+
+const KEYRING_SERVICE: &str = "az-interface";
 
 #[derive(Serialize, Deserialize)]
 pub struct AIRequest {
@@ -39,57 +44,353 @@ pub struct ImageGenerationResponse {
     pub error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AITokenEvent {
+    pub request_id: String,
+    pub delta: String,
+}
+
+#[derive(Clone, Copy)]
+enum Provider {
+    Gemini,
+    OpenAI,
+    Anthropic,
+}
+
+impl Provider {
+    fn from_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+        if model.starts_with("gemini") {
+            Provider::Gemini
+        } else if model.starts_with("gpt") {
+            Provider::OpenAI
+        } else {
+            Provider::Anthropic
+        }
+    }
+
+    fn keyring_account(&self) -> &'static str {
+        match self {
+            Provider::Gemini => "gemini",
+            Provider::OpenAI => "openai",
+            Provider::Anthropic => "anthropic",
+        }
+    }
+
+    /// Maps a user-facing provider name (as saved/tested from the frontend) to the
+    /// canonical provider. Accepts the same aliases as `from_model` plus the plain
+    /// provider brand names, so `save_api_key`/`test_api_connection` always resolve
+    /// to the same keyring account as `api_key_for` does at call time.
+    fn from_alias(alias: &str) -> Option<Self> {
+        match alias.to_lowercase().as_str() {
+            "gemini" | "google" => Some(Provider::Gemini),
+            "openai" | "gpt" => Some(Provider::OpenAI),
+            "anthropic" | "claude" => Some(Provider::Anthropic),
+            _ => None,
+        }
+    }
+}
+
+fn api_key_for(provider: Provider) -> Result<String, String> {
+    keyring::Entry::new(KEYRING_SERVICE, provider.keyring_account())
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| format!("No API key stored for provider: {}", e))
+}
+
+/// Reads a provider HTTP response body once and, if the status wasn't successful,
+/// turns the provider's JSON error payload (or raw body, if it isn't JSON) into an
+/// `Err` instead of letting the caller index into an error body as if it succeeded.
+async fn parse_provider_response(response: reqwest::Response) -> Result<serde_json::Value, String> {
+    let status = response.status();
+    let text = response.text().await.map_err(|e| e.to_string())?;
+
+    if !status.is_success() {
+        let message = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|body| body["error"]["message"].as_str().map(|s| s.to_string()))
+            .unwrap_or(text);
+        return Err(format!("provider request failed ({}): {}", status, message));
+    }
+
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
 #[command]
 pub async fn call_gemini_api(request: AIRequest) -> Result<AIResponse, String> {
-    // This is a mock implementation
-    // In a real implementation, you'd make HTTP requests to the Gemini API
-    
     let model = request.model.unwrap_or_else(|| "gemini-pro".to_string());
-    
-    // Simulate API call delay
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-    
-    // Mock response based on the prompt
-    let content = if request.prompt.to_lowercase().contains("hello") {
-        "Hello! I'm Gemini, an AI assistant. How can I help you today?"
-    } else if request.prompt.to_lowercase().contains("code") {
-        "Here's a sample code snippet:\n\n```rust\nfn main() {\n    println!(\"Hello, world!\");\n}\n```"
-    } else {
-        "I understand your request. Here's a helpful response based on your prompt."
+    let provider = Provider::from_model(&model);
+    let api_key = api_key_for(provider)?;
+    let client = reqwest::Client::new();
+
+    let (content, usage) = match provider {
+        Provider::Gemini => {
+            let url = format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+                model, api_key
+            );
+            let body = json!({
+                "contents": [{ "parts": [{ "text": request.prompt }] }],
+                "generationConfig": {
+                    "temperature": request.temperature,
+                    "maxOutputTokens": request.max_tokens,
+                }
+            });
+            let response = client.post(&url).json(&body).send().await.map_err(|e| e.to_string())?;
+            let response = parse_provider_response(response).await?;
+
+            let content = response["candidates"][0]["content"]["parts"][0]["text"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let usage_meta = &response["usageMetadata"];
+            let usage = AIUsage {
+                prompt_tokens: usage_meta["promptTokenCount"].as_u64().unwrap_or(0) as u32,
+                completion_tokens: usage_meta["candidatesTokenCount"].as_u64().unwrap_or(0) as u32,
+                total_tokens: usage_meta["totalTokenCount"].as_u64().unwrap_or(0) as u32,
+            };
+            (content, usage)
+        }
+        Provider::OpenAI => {
+            let response = client
+                .post("https://api.openai.com/v1/chat/completions")
+                .bearer_auth(&api_key)
+                .json(&json!({
+                    "model": model,
+                    "messages": [{ "role": "user", "content": request.prompt }],
+                    "temperature": request.temperature,
+                    "max_tokens": request.max_tokens,
+                }))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            let response = parse_provider_response(response).await?;
+
+            let content = response["choices"][0]["message"]["content"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let usage = AIUsage {
+                prompt_tokens: response["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as u32,
+                completion_tokens: response["usage"]["completion_tokens"].as_u64().unwrap_or(0) as u32,
+                total_tokens: response["usage"]["total_tokens"].as_u64().unwrap_or(0) as u32,
+            };
+            (content, usage)
+        }
+        Provider::Anthropic => {
+            let response = client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &api_key)
+                .header("anthropic-version", "2023-06-01")
+                .json(&json!({
+                    "model": model,
+                    "max_tokens": request.max_tokens.unwrap_or(1024),
+                    "messages": [{ "role": "user", "content": request.prompt }],
+                }))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            let response = parse_provider_response(response).await?;
+
+            let content = response["content"][0]["text"].as_str().unwrap_or_default().to_string();
+            let usage = AIUsage {
+                prompt_tokens: response["usage"]["input_tokens"].as_u64().unwrap_or(0) as u32,
+                completion_tokens: response["usage"]["output_tokens"].as_u64().unwrap_or(0) as u32,
+                total_tokens: (response["usage"]["input_tokens"].as_u64().unwrap_or(0)
+                    + response["usage"]["output_tokens"].as_u64().unwrap_or(0)) as u32,
+            };
+            (content, usage)
+        }
     };
-    
+
     Ok(AIResponse {
-        content: content.to_string(),
+        content,
         model,
-        usage: Some(AIUsage {
-            prompt_tokens: request.prompt.len() as u32 / 4,
-            completion_tokens: content.len() as u32 / 4,
-            total_tokens: (request.prompt.len() + content.len()) as u32 / 4,
-        }),
+        usage: Some(usage),
         error: None,
     })
 }
 
+fn emit_token(app: &AppHandle, request_id: &str, delta: &str) {
+    let _ = app.emit(
+        "ai-token",
+        AITokenEvent {
+            request_id: request_id.to_string(),
+            delta: delta.to_string(),
+        },
+    );
+}
+
+#[command]
+pub async fn call_ai_stream(
+    request_id: String,
+    request: AIRequest,
+    app: AppHandle,
+) -> Result<AIUsage, String> {
+    let model = request.model.unwrap_or_else(|| "gpt-3.5-turbo".to_string());
+    let provider = Provider::from_model(&model);
+    let api_key = api_key_for(provider)?;
+    let client = reqwest::Client::new();
+
+    let response = match provider {
+        Provider::OpenAI => {
+            client
+                .post("https://api.openai.com/v1/chat/completions")
+                .bearer_auth(&api_key)
+                .json(&json!({
+                    "model": model,
+                    "messages": [{ "role": "user", "content": request.prompt }],
+                    "stream": true,
+                    "stream_options": { "include_usage": true },
+                }))
+                .send()
+                .await
+        }
+        Provider::Anthropic => {
+            client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &api_key)
+                .header("anthropic-version", "2023-06-01")
+                .json(&json!({
+                    "model": model,
+                    "max_tokens": request.max_tokens.unwrap_or(1024),
+                    "messages": [{ "role": "user", "content": request.prompt }],
+                    "stream": true,
+                }))
+                .send()
+                .await
+        }
+        Provider::Gemini => {
+            // `alt=sse` is required to get `data: `-delimited chunks; without it Gemini
+            // returns a single JSON array over chunked transfer that this parser can't read.
+            let url = format!(
+                "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+                model, api_key
+            );
+            client
+                .post(&url)
+                .json(&json!({ "contents": [{ "parts": [{ "text": request.prompt }] }] }))
+                .send()
+                .await
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("provider request failed ({}): {}", status, body));
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut prompt_tokens = 0u32;
+    let mut completion_tokens = 0u32;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        for line in String::from_utf8_lossy(&chunk).lines() {
+            let Some(payload) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            if payload == "[DONE]" {
+                continue;
+            }
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(payload) else {
+                continue;
+            };
+
+            match provider {
+                Provider::OpenAI => {
+                    if let Some(delta) = event["choices"][0]["delta"]["content"].as_str() {
+                        emit_token(&app, &request_id, delta);
+                    }
+                    if let Some(usage) = event.get("usage").filter(|u| !u.is_null()) {
+                        prompt_tokens = usage["prompt_tokens"].as_u64().unwrap_or(0) as u32;
+                        completion_tokens = usage["completion_tokens"].as_u64().unwrap_or(0) as u32;
+                    }
+                }
+                Provider::Anthropic => match event["type"].as_str() {
+                    Some("message_start") => {
+                        prompt_tokens = event["message"]["usage"]["input_tokens"].as_u64().unwrap_or(0) as u32;
+                    }
+                    Some("content_block_delta") => {
+                        if let Some(delta) = event["delta"]["text"].as_str() {
+                            emit_token(&app, &request_id, delta);
+                        }
+                    }
+                    Some("message_delta") => {
+                        completion_tokens = event["usage"]["output_tokens"].as_u64().unwrap_or(0) as u32;
+                    }
+                    _ => {}
+                },
+                Provider::Gemini => {
+                    if let Some(delta) = event["candidates"][0]["content"]["parts"][0]["text"].as_str() {
+                        emit_token(&app, &request_id, delta);
+                    }
+                    if let Some(usage_meta) = event.get("usageMetadata") {
+                        prompt_tokens = usage_meta["promptTokenCount"].as_u64().unwrap_or(0) as u32;
+                        completion_tokens = usage_meta["candidatesTokenCount"].as_u64().unwrap_or(0) as u32;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(AIUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    })
+}
+
 #[command]
 pub async fn generate_image(request: ImageGenerationRequest) -> Result<ImageGenerationResponse, String> {
-    // Mock image generation
-    // In a real implementation, you'd call an image generation API
-    
-    let size = request.size.unwrap_or_else(|| "1024x1024".to_string());
-    
-    // Simulate processing time
-    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-    
-    // Mock response
+    let api_key = api_key_for(Provider::OpenAI)?;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post("https://api.openai.com/v1/images/generations")
+        .bearer_auth(&api_key)
+        .json(&json!({
+            "prompt": request.prompt,
+            "size": request.size.unwrap_or_else(|| "1024x1024".to_string()),
+            "quality": request.quality.unwrap_or_else(|| "standard".to_string()),
+            "n": 1,
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let response = parse_provider_response(response).await?;
+
+    let image_url = response["data"][0]["url"].as_str().map(|s| s.to_string());
+
     Ok(ImageGenerationResponse {
-        image_url: Some(format!("https://example.com/generated-image-{}.png", chrono::Utc::now().timestamp())),
-        image_path: Some(format!("./generated/images/image-{}.png", chrono::Utc::now().timestamp())),
+        image_url,
+        image_path: None,
         error: None,
     })
 }
 
 #[command]
-pub async fn analyze_system_state() -> Result<String, String> {
+pub async fn analyze_system_state(
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+) -> Result<String, String> {
+    if let Some(connection_id) = connection {
+        return match connections
+            .dispatch(
+                &connection_id,
+                RemoteRequest::ExecuteCommand {
+                    command: "uptime && free -m && df -h".to_string(),
+                },
+            )
+            .await?
+        {
+            RemoteResponse::CommandOutput(output) => Ok(output),
+            RemoteResponse::Error(e) => Err(e),
+            _ => Err("Unexpected response from remote agent".to_string()),
+        };
+    }
+
     // Mock system analysis using AI
     let analysis = r#"
 System Analysis Report:
@@ -102,7 +403,7 @@ System Analysis Report:
   * Monitor memory usage for potential optimization
   * System health: Good
 "#;
-    
+
     Ok(analysis.to_string())
 }
 
@@ -121,29 +422,44 @@ pub async fn get_ai_models() -> Result<Vec<String>, String> {
 
 #[command]
 pub async fn save_api_key(provider: String, api_key: String) -> Result<(), String> {
-    // In a real implementation, you'd securely store the API key
-    // For now, just return success
-    println!("API key saved for provider: {}", provider);
+    let provider = Provider::from_alias(&provider).ok_or_else(|| format!("Unknown provider: {}", provider))?;
+    let entry = keyring::Entry::new(KEYRING_SERVICE, provider.keyring_account()).map_err(|e| e.to_string())?;
+    entry.set_password(&api_key).map_err(|e| e.to_string())?;
+    tracing::info!(provider = provider.keyring_account(), "API key saved to OS keychain");
     Ok(())
 }
 
 #[command]
 pub async fn test_api_connection(provider: String) -> Result<bool, String> {
-    // Mock API connection test
-    match provider.to_lowercase().as_str() {
-        "gemini" | "google" => {
-            // Simulate connection test
-            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-            Ok(true)
-        }
-        "openai" | "gpt" => {
-            tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
-            Ok(true)
+    let Some(provider) = Provider::from_alias(&provider) else {
+        return Ok(false);
+    };
+
+    let Ok(api_key) = api_key_for(provider) else {
+        return Ok(false);
+    };
+
+    let client = reqwest::Client::new();
+    let response = match provider {
+        Provider::Gemini => {
+            client
+                .get(format!(
+                    "https://generativelanguage.googleapis.com/v1beta/models?key={}",
+                    api_key
+                ))
+                .send()
+                .await
         }
-        "anthropic" | "claude" => {
-            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-            Ok(true)
+        Provider::OpenAI => client.get("https://api.openai.com/v1/models").bearer_auth(&api_key).send().await,
+        Provider::Anthropic => {
+            client
+                .get("https://api.anthropic.com/v1/models")
+                .header("x-api-key", &api_key)
+                .header("anthropic-version", "2023-06-01")
+                .send()
+                .await
         }
-        _ => Ok(false),
-    }
+    };
+
+    Ok(response.map(|r| r.status().is_success()).unwrap_or(false))
 }
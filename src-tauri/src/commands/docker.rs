@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use bollard::container::{
+    ListContainersOptions, LogsOptions, RestartContainerOptions, StartContainerOptions,
+    StopContainerOptions,
+};
+use bollard::image::CreateImageOptions;
+use bollard::models::{HostConfig, PortBinding};
+use bollard::Docker;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+
+const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+
+#[derive(Debug, Deserialize)]
+struct ComposeFile {
+    services: HashMap<String, ComposeServiceDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposeServiceDef {
+    image: String,
+    #[serde(default)]
+    ports: Vec<String>,
+    #[serde(default)]
+    environment: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ComposeServiceStatus {
+    pub name: String,
+    pub container_id: Option<String>,
+    pub state: String,
+    pub health: Option<String>,
+    pub ports: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DockerLogEvent {
+    pub service: String,
+    pub line: String,
+}
+
+fn project_name(compose_path: &str) -> String {
+    std::path::Path::new(compose_path)
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("default")
+        .to_string()
+}
+
+async fn connect() -> Result<Docker, String> {
+    Docker::connect_with_local_defaults().map_err(|e| e.to_string())
+}
+
+async fn load_compose_file(compose_path: &str) -> Result<ComposeFile, String> {
+    let raw = tokio::fs::read_to_string(compose_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_yaml::from_str(&raw).map_err(|e| e.to_string())
+}
+
+/// Pulls `image` if the Docker Engine doesn't already have it locally, mirroring the
+/// implicit pull `docker-compose up` does for images not yet present.
+async fn ensure_image(docker: &Docker, image: &str) -> Result<(), String> {
+    if docker.inspect_image(image).await.is_ok() {
+        return Ok(());
+    }
+
+    let mut stream = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: image,
+            ..Default::default()
+        }),
+        None,
+        None,
+    );
+
+    while let Some(progress) = stream.next().await {
+        progress.map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Parses a compose `ports` entry (`"8080:80"`, `"8080:80/udp"`, or bare `"80"` for an
+/// expose-only port) into the container port/protocol and an optional host port to
+/// publish it on.
+fn parse_port_mapping(spec: &str) -> (String, Option<String>) {
+    let (port_part, proto) = match spec.rsplit_once('/') {
+        Some((p, proto)) => (p, proto),
+        None => (spec, "tcp"),
+    };
+
+    match port_part.split_once(':') {
+        Some((host, container)) => (format!("{}/{}", container, proto), Some(host.to_string())),
+        None => (format!("{}/{}", port_part, proto), None),
+    }
+}
+
+fn build_host_config(ports: &[String]) -> (HashMap<String, HashMap<(), ()>>, HostConfig) {
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
+
+    for spec in ports {
+        let (container_port, host_port) = parse_port_mapping(spec);
+        exposed_ports.insert(container_port.clone(), HashMap::new());
+
+        if let Some(host_port) = host_port {
+            port_bindings.insert(
+                container_port,
+                Some(vec![PortBinding {
+                    host_ip: None,
+                    host_port: Some(host_port),
+                }]),
+            );
+        }
+    }
+
+    let host_config = HostConfig {
+        port_bindings: Some(port_bindings),
+        ..Default::default()
+    };
+
+    (exposed_ports, host_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_port_mapping_bare_container_port() {
+        assert_eq!(parse_port_mapping("80"), ("80/tcp".to_string(), None));
+    }
+
+    #[test]
+    fn parse_port_mapping_host_and_container() {
+        assert_eq!(
+            parse_port_mapping("8080:80"),
+            ("80/tcp".to_string(), Some("8080".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_port_mapping_with_protocol() {
+        assert_eq!(
+            parse_port_mapping("8080:80/udp"),
+            ("80/udp".to_string(), Some("8080".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_port_mapping_bare_port_with_protocol() {
+        assert_eq!(parse_port_mapping("53/udp"), ("53/udp".to_string(), None));
+    }
+
+    #[test]
+    fn build_host_config_exposes_and_binds_published_ports() {
+        let (exposed_ports, host_config) =
+            build_host_config(&["8080:80".to_string(), "53/udp".to_string()]);
+
+        assert!(exposed_ports.contains_key("80/tcp"));
+        assert!(exposed_ports.contains_key("53/udp"));
+
+        let bindings = host_config.port_bindings.unwrap();
+        assert_eq!(
+            bindings["80/tcp"].as_ref().unwrap()[0].host_port,
+            Some("8080".to_string())
+        );
+        // Bare (expose-only) ports get no binding entry at all.
+        assert!(!bindings.contains_key("53/udp"));
+    }
+}
+
+#[command]
+pub async fn compose_up(compose_path: String) -> Result<Vec<ComposeServiceStatus>, String> {
+    let docker = connect().await?;
+    let compose = load_compose_file(&compose_path).await?;
+    let project = project_name(&compose_path);
+
+    let mut results = Vec::new();
+
+    for (service_name, def) in compose.services {
+        let container_name = format!("{}_{}", project, service_name);
+
+        let mut labels = HashMap::new();
+        labels.insert(COMPOSE_PROJECT_LABEL.to_string(), project.clone());
+        labels.insert(COMPOSE_SERVICE_LABEL.to_string(), service_name.clone());
+
+        let env: Vec<String> = def
+            .environment
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+
+        ensure_image(&docker, &def.image).await?;
+        let (exposed_ports, host_config) = build_host_config(&def.ports);
+
+        let config = bollard::container::Config {
+            image: Some(def.image.clone()),
+            env: Some(env),
+            labels: Some(labels),
+            exposed_ports: Some(exposed_ports),
+            host_config: Some(host_config),
+            ..Default::default()
+        };
+
+        let create_options = bollard::container::CreateContainerOptions {
+            name: container_name.clone(),
+            platform: None,
+        };
+
+        let container = docker
+            .create_container(Some(create_options), config)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        docker
+            .start_container(&container.id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        results.push(ComposeServiceStatus {
+            name: service_name,
+            container_id: Some(container.id),
+            state: "running".to_string(),
+            health: None,
+            ports: def.ports,
+        });
+    }
+
+    Ok(results)
+}
+
+#[command]
+pub async fn compose_down(compose_path: String) -> Result<(), String> {
+    let docker = connect().await?;
+    let project = project_name(&compose_path);
+
+    let mut filters = HashMap::new();
+    filters.insert(
+        "label".to_string(),
+        vec![format!("{}={}", COMPOSE_PROJECT_LABEL, project)],
+    );
+
+    let containers = docker
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for container in containers {
+        if let Some(id) = container.id {
+            docker
+                .stop_container(&id, None::<StopContainerOptions>)
+                .await
+                .map_err(|e| e.to_string())?;
+            docker
+                .remove_container(&id, None)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+pub async fn compose_ps(compose_path: String) -> Result<Vec<ComposeServiceStatus>, String> {
+    let docker = connect().await?;
+    let project = project_name(&compose_path);
+
+    let mut filters = HashMap::new();
+    filters.insert(
+        "label".to_string(),
+        vec![format!("{}={}", COMPOSE_PROJECT_LABEL, project)],
+    );
+
+    let containers = docker
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters,
+            ..Default::default()
+        }))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(containers
+        .into_iter()
+        .map(|container| {
+            let labels = container.labels.unwrap_or_default();
+            let name = labels
+                .get(COMPOSE_SERVICE_LABEL)
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string());
+            let ports = container
+                .ports
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|p| p.public_port.map(|port| port.to_string()))
+                .collect();
+
+            ComposeServiceStatus {
+                name,
+                container_id: container.id,
+                state: container.state.unwrap_or_else(|| "unknown".to_string()),
+                health: container.status,
+                ports,
+            }
+        })
+        .collect())
+}
+
+#[command]
+pub async fn restart_container(container_id: String) -> Result<(), String> {
+    let docker = connect().await?;
+    docker
+        .restart_container(&container_id, None::<RestartContainerOptions>)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn stream_container_logs(
+    container_id: String,
+    service_name: String,
+    app: AppHandle,
+) -> Result<(), String> {
+    let docker = connect().await?;
+
+    let mut stream = docker.logs(
+        &container_id,
+        Some(LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        }),
+    );
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(chunk) = stream.next().await {
+            if let Ok(log_output) = chunk {
+                let _ = app.emit(
+                    "docker-log",
+                    DockerLogEvent {
+                        service: service_name.clone(),
+                        line: log_output.to_string(),
+                    },
+                );
+            }
+        }
+    });
+
+    Ok(())
+}
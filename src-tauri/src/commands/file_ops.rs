@@ -1,7 +1,11 @@
-use tauri::command;
-use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
+use tauri::command;
+use tracing::{instrument, warn};
+
+use crate::commands::remote::{ConnectionRegistry, RemoteRequest, RemoteResponse};
+use crate::commands::storage::{resolve_store, StorageConfig};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct FileEntry {
@@ -20,95 +24,94 @@ pub struct FileContent {
 }
 
 #[command]
-pub async fn read_file_content(path: String) -> Result<FileContent, String> {
-    let file_path = Path::new(&path);
-    
-    if !file_path.exists() {
-        return Err(format!("File not found: {}", path));
-    }
-    
-    if !file_path.is_file() {
-        return Err(format!("Path is not a file: {}", path));
-    }
-    
-    match fs::read_to_string(&path) {
-        Ok(content) => {
-            let metadata = fs::metadata(&path).map_err(|e| e.to_string())?;
-            Ok(FileContent {
-                content,
-                encoding: "utf-8".to_string(),
-                size: metadata.len(),
-            })
-        }
-        Err(e) => Err(e.to_string()),
+#[instrument(skip(connections, storage))]
+pub async fn read_file_content(
+    path: String,
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<FileContent, String> {
+    if let Some(connection_id) = connection {
+        return match connections
+            .dispatch(&connection_id, RemoteRequest::ReadFile { path })
+            .await?
+        {
+            RemoteResponse::FileContent(content) => Ok(content),
+            RemoteResponse::Error(e) => Err(e),
+            _ => Err("Unexpected response from remote agent".to_string()),
+        };
     }
+
+    let (store, key) = resolve_store(&path, &storage)?;
+    let data = store.read(&key).await.map_err(|e| {
+        warn!(path = %path, error = %e, "failed to read file");
+        e
+    })?;
+    let size = data.len() as u64;
+    let content = String::from_utf8(data).map_err(|e| {
+        warn!(path = %path, error = %e, "file content is not valid utf-8");
+        e.to_string()
+    })?;
+
+    Ok(FileContent {
+        content,
+        encoding: "utf-8".to_string(),
+        size,
+    })
 }
 
 #[command]
-pub async fn write_file_content(path: String, content: String) -> Result<(), String> {
-    let file_path = Path::new(&path);
-    
-    // Create parent directories if they don't exist
-    if let Some(parent) = file_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
+#[instrument(skip(content, connections, storage))]
+pub async fn write_file_content(
+    path: String,
+    content: String,
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<(), String> {
+    if let Some(connection_id) = connection {
+        return match connections
+            .dispatch(&connection_id, RemoteRequest::WriteFile { path, content })
+            .await?
+        {
+            RemoteResponse::Ack => Ok(()),
+            RemoteResponse::Error(e) => Err(e),
+            _ => Err("Unexpected response from remote agent".to_string()),
+        };
     }
-    
-    fs::write(&path, content).map_err(|e| e.to_string())
+
+    let (store, key) = resolve_store(&path, &storage)?;
+    store.write(&key, content.into_bytes()).await.map_err(|e| {
+        warn!(path = %path, error = %e, "failed to write file");
+        e
+    })
 }
 
 #[command]
-pub async fn list_directory(path: String) -> Result<Vec<FileEntry>, String> {
-    let dir_path = Path::new(&path);
-    
-    if !dir_path.exists() {
-        return Err(format!("Directory not found: {}", path));
-    }
-    
-    if !dir_path.is_dir() {
-        return Err(format!("Path is not a directory: {}", path));
+#[instrument(skip(connections, storage))]
+pub async fn list_directory(
+    path: String,
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<Vec<FileEntry>, String> {
+    if let Some(connection_id) = connection {
+        return match connections
+            .dispatch(&connection_id, RemoteRequest::ListDirectory { path })
+            .await?
+        {
+            RemoteResponse::DirectoryListing(entries) => Ok(entries),
+            RemoteResponse::Error(e) => Err(e),
+            _ => Err("Unexpected response from remote agent".to_string()),
+        };
     }
-    
-    let mut entries = Vec::new();
-    
-    match fs::read_dir(&path) {
-        Ok(entries_iter) => {
-            for entry in entries_iter {
-                match entry {
-                    Ok(entry) => {
-                        let path = entry.path();
-                        let name = path.file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-                        
-                        let full_path = path.to_string_lossy().to_string();
-                        let is_directory = path.is_dir();
-                        
-                        let metadata = fs::metadata(&path).ok();
-                        let size = if is_directory { None } else { metadata.map(|m| m.len()) };
-                        let modified = metadata
-                            .and_then(|m| m.modified().ok())
-                            .map(|t| format!("{:?}", t));
-                        
-                        entries.push(FileEntry {
-                            name,
-                            path: full_path,
-                            is_directory,
-                            size,
-                            modified,
-                        });
-                    }
-                    Err(e) => {
-                        eprintln!("Error reading directory entry: {}", e);
-                    }
-                }
-            }
-        }
-        Err(e) => return Err(e.to_string()),
-    }
-    
+
+    let (store, key) = resolve_store(&path, &storage)?;
+    let mut entries = store.list(&key).await.map_err(|e| {
+        warn!(path = %path, error = %e, "failed to list directory");
+        e
+    })?;
+
     // Sort entries: directories first, then files
     entries.sort_by(|a, b| {
         if a.is_directory != b.is_directory {
@@ -117,56 +120,61 @@ pub async fn list_directory(path: String) -> Result<Vec<FileEntry>, String> {
             a.name.to_lowercase().cmp(&b.name.to_lowercase())
         }
     });
-    
+
     Ok(entries)
 }
 
 #[command]
+#[instrument]
 pub async fn create_directory(path: String) -> Result<(), String> {
-    fs::create_dir_all(&path).map_err(|e| e.to_string())
+    tokio::fs::create_dir_all(&path).await.map_err(|e| {
+        warn!(path = %path, error = %e, "failed to create directory");
+        e.to_string()
+    })
 }
 
 #[command]
-pub async fn delete_file(path: String) -> Result<(), String> {
-    let file_path = Path::new(&path);
-    
-    if !file_path.exists() {
-        return Err(format!("File not found: {}", path));
-    }
-    
-    if file_path.is_dir() {
-        fs::remove_dir_all(&path).map_err(|e| e.to_string())
-    } else {
-        fs::remove_file(&path).map_err(|e| e.to_string())
-    }
+#[instrument(skip(storage))]
+pub async fn delete_file(
+    path: String,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<(), String> {
+    let (store, key) = resolve_store(&path, &storage)?;
+    store.delete(&key).await.map_err(|e| {
+        warn!(path = %path, error = %e, "failed to delete file");
+        e
+    })
 }
 
 #[command]
-pub async fn file_exists(path: String) -> Result<bool, String> {
-    Ok(Path::new(&path).exists())
+pub async fn file_exists(
+    path: String,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<bool, String> {
+    let (store, key) = resolve_store(&path, &storage)?;
+    store.exists(&key).await
 }
 
 #[command]
+#[instrument]
 pub async fn get_file_info(path: String) -> Result<FileEntry, String> {
-    let file_path = Path::new(&path);
-    
-    if !file_path.exists() {
-        return Err(format!("File not found: {}", path));
-    }
-    
-    let name = file_path.file_name()
+    let file_path = PathBuf::from(&path);
+
+    let metadata = tokio::fs::metadata(&file_path).await.map_err(|e| {
+        warn!(path = %path, error = %e, "file not found");
+        format!("File not found: {}", path)
+    })?;
+
+    let name = file_path
+        .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unknown")
         .to_string();
-    
-    let is_directory = file_path.is_dir();
-    let metadata = fs::metadata(&path).map_err(|e| e.to_string())?;
+
+    let is_directory = metadata.is_dir();
     let size = if is_directory { None } else { Some(metadata.len()) };
-    let modified = metadata
-        .modified()
-        .ok()
-        .map(|t| format!("{:?}", t));
-    
+    let modified = metadata.modified().ok().map(|t| format!("{:?}", t));
+
     Ok(FileEntry {
         name,
         path: path.clone(),
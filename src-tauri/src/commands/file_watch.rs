@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use uuid::Uuid;
+
+/// How long to accumulate raw filesystem events before flushing a batch to the frontend.
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(250);
+
+struct ActiveWatcher {
+    // Kept alive only so the OS watch handle is dropped (and cleaned up) on `unwatch_path`.
+    _watcher: RecommendedWatcher,
+}
+
+#[derive(Default)]
+pub struct WatcherRegistry(Mutex<HashMap<String, ActiveWatcher>>);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FileChangeEvent {
+    pub watcher_id: String,
+    pub kind: String,
+    pub paths: Vec<String>,
+}
+
+fn event_kind_label(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Create(_) => "create",
+        EventKind::Modify(_) => "modify",
+        EventKind::Remove(_) => "remove",
+        _ => "other",
+    }
+}
+
+fn build_ignore_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| e.to_string())?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn spawn_debounced_forwarder(
+    watcher_id: String,
+    app: AppHandle,
+    ignore: GlobSet,
+    rx: Receiver<notify::Result<Event>>,
+) {
+    std::thread::spawn(move || {
+        let mut pending: HashMap<String, (&'static str, Instant)> = HashMap::new();
+
+        loop {
+            match rx.recv_timeout(DEBOUNCE_WINDOW) {
+                Ok(Ok(event)) => {
+                    let kind = event_kind_label(&event.kind);
+                    for path in event.paths {
+                        let path_str = path.to_string_lossy().to_string();
+                        if ignore.is_match(&path) {
+                            continue;
+                        }
+                        pending.insert(path_str, (kind, Instant::now()));
+                    }
+                }
+                Ok(Err(_)) => continue,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            if pending.is_empty() {
+                continue;
+            }
+
+            let ready: Vec<(String, &'static str)> = pending
+                .iter()
+                .filter(|(_, (_, seen))| seen.elapsed() >= DEBOUNCE_WINDOW)
+                .map(|(path, (kind, _))| (path.clone(), *kind))
+                .collect();
+
+            if ready.is_empty() {
+                continue;
+            }
+
+            let mut by_kind: HashMap<&'static str, Vec<String>> = HashMap::new();
+            for (path, kind) in &ready {
+                by_kind.entry(kind).or_default().push(path.clone());
+            }
+
+            for (kind, paths) in by_kind {
+                let _ = app.emit(
+                    "file-watch-event",
+                    FileChangeEvent {
+                        watcher_id: watcher_id.clone(),
+                        kind: kind.to_string(),
+                        paths,
+                    },
+                );
+            }
+
+            for (path, _) in ready {
+                pending.remove(&path);
+            }
+        }
+    });
+}
+
+#[command]
+pub async fn watch_path(
+    path: String,
+    ignore_globs: Option<Vec<String>>,
+    app: AppHandle,
+    registry: tauri::State<'_, WatcherRegistry>,
+) -> Result<String, String> {
+    let root: PathBuf = PathBuf::from(&path);
+    if !root.exists() {
+        return Err(format!("Path not found: {}", path));
+    }
+
+    let ignore = build_ignore_set(&ignore_globs.unwrap_or_default())?;
+    let (tx, rx) = channel();
+
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+
+    let watcher_id = Uuid::new_v4().to_string();
+    spawn_debounced_forwarder(watcher_id.clone(), app, ignore, rx);
+
+    registry
+        .0
+        .lock()
+        .map_err(|_| "watcher registry poisoned".to_string())?
+        .insert(watcher_id.clone(), ActiveWatcher { _watcher: watcher });
+
+    Ok(watcher_id)
+}
+
+#[command]
+pub async fn unwatch_path(
+    watcher_id: String,
+    registry: tauri::State<'_, WatcherRegistry>,
+) -> Result<(), String> {
+    let removed = registry
+        .0
+        .lock()
+        .map_err(|_| "watcher registry poisoned".to_string())?
+        .remove(&watcher_id);
+
+    if removed.is_none() {
+        return Err(format!("No active watcher with id: {}", watcher_id));
+    }
+
+    Ok(())
+}
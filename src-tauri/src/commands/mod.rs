@@ -1,7 +1,19 @@
 pub mod system;
 pub mod file_ops;
+pub mod file_watch;
+pub mod process;
+pub mod remote;
+pub mod storage;
+pub mod thumbnail;
+pub mod docker;
 pub mod ai_integration;
 
 pub use system::*;
 pub use file_ops::*;
+pub use file_watch::*;
+pub use process::*;
+pub use remote::*;
+pub use storage::*;
+pub use thumbnail::*;
+pub use docker::*;
 pub use ai_integration::*;
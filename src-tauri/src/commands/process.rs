@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+/// How often the waiter task polls a process for a natural exit.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcessOutputEvent {
+    pub process_id: String,
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcessExitEvent {
+    pub process_id: String,
+    pub code: Option<i32>,
+}
+
+enum ManagedProcess {
+    Simple {
+        child: std::process::Child,
+    },
+    Pty {
+        master: Box<dyn MasterPty + Send>,
+        writer: Box<dyn Write + Send>,
+        child: Box<dyn portable_pty::Child + Send + Sync>,
+    },
+}
+
+#[derive(Default)]
+pub struct ProcessRegistry(Mutex<HashMap<String, ManagedProcess>>);
+
+fn spawn_reader<R: Read + Send + 'static>(
+    mut reader: R,
+    app: AppHandle,
+    process_id: String,
+    event_name: &'static str,
+) {
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let data = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let _ = app.emit(
+                        event_name,
+                        ProcessOutputEvent {
+                            process_id: process_id.clone(),
+                            data,
+                        },
+                    );
+                }
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Polls a process for a natural exit and, once it happens, emits `process-exit` and
+/// removes it from the registry. `kill_process` races this harmlessly: whichever one
+/// removes the entry first wins, and the other finds it already gone and stops.
+fn spawn_exit_waiter(app: AppHandle, process_id: String) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+
+            let registry = app.state::<ProcessRegistry>();
+            let mut guard = match registry.0.lock() {
+                Ok(guard) => guard,
+                Err(_) => return,
+            };
+
+            let Some(process) = guard.get_mut(&process_id) else {
+                // Already removed (e.g. by `kill_process`).
+                return;
+            };
+
+            let exit_code = match process {
+                ManagedProcess::Simple { child } => match child.try_wait() {
+                    Ok(Some(status)) => Some(status.code()),
+                    Ok(None) => None,
+                    Err(_) => return,
+                },
+                ManagedProcess::Pty { child, .. } => match child.try_wait() {
+                    Ok(Some(status)) => Some(Some(status.exit_code() as i32)),
+                    Ok(None) => None,
+                    Err(_) => return,
+                },
+            };
+
+            let Some(code) = exit_code else {
+                continue;
+            };
+
+            guard.remove(&process_id);
+            drop(guard);
+
+            let _ = app.emit("process-exit", ProcessExitEvent { process_id, code });
+            return;
+        }
+    });
+}
+
+#[command]
+pub async fn spawn_process(
+    command: String,
+    args: Vec<String>,
+    use_pty: bool,
+    app: AppHandle,
+    registry: tauri::State<'_, ProcessRegistry>,
+) -> Result<String, String> {
+    let process_id = Uuid::new_v4().to_string();
+
+    if use_pty {
+        let pty_system = native_pty_system();
+        let pair = pty_system
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| e.to_string())?;
+
+        let mut cmd = CommandBuilder::new(&command);
+        cmd.args(&args);
+
+        let child = pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+        drop(pair.slave);
+
+        let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
+        let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
+
+        spawn_reader(reader, app.clone(), process_id.clone(), "process-stdout");
+
+        registry
+            .0
+            .lock()
+            .map_err(|_| "process registry poisoned".to_string())?
+            .insert(
+                process_id.clone(),
+                ManagedProcess::Pty {
+                    master: pair.master,
+                    writer,
+                    child,
+                },
+            );
+
+        spawn_exit_waiter(app, process_id.clone());
+    } else {
+        let mut child = std::process::Command::new(&command)
+            .args(&args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        if let Some(stdout) = child.stdout.take() {
+            spawn_reader(stdout, app.clone(), process_id.clone(), "process-stdout");
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_reader(stderr, app.clone(), process_id.clone(), "process-stderr");
+        }
+
+        registry
+            .0
+            .lock()
+            .map_err(|_| "process registry poisoned".to_string())?
+            .insert(process_id.clone(), ManagedProcess::Simple { child });
+
+        spawn_exit_waiter(app, process_id.clone());
+    }
+
+    Ok(process_id)
+}
+
+#[command]
+pub async fn write_process_stdin(
+    process_id: String,
+    data: String,
+    registry: tauri::State<'_, ProcessRegistry>,
+) -> Result<(), String> {
+    let mut guard = registry
+        .0
+        .lock()
+        .map_err(|_| "process registry poisoned".to_string())?;
+
+    match guard
+        .get_mut(&process_id)
+        .ok_or_else(|| format!("No active process with id: {}", process_id))?
+    {
+        ManagedProcess::Simple { child } => {
+            let stdin = child
+                .stdin
+                .as_mut()
+                .ok_or("process has no stdin pipe")?;
+            stdin.write_all(data.as_bytes()).map_err(|e| e.to_string())
+        }
+        ManagedProcess::Pty { writer, .. } => {
+            writer.write_all(data.as_bytes()).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[command]
+pub async fn resize_pty(
+    process_id: String,
+    rows: u16,
+    cols: u16,
+    registry: tauri::State<'_, ProcessRegistry>,
+) -> Result<(), String> {
+    let guard = registry
+        .0
+        .lock()
+        .map_err(|_| "process registry poisoned".to_string())?;
+
+    match guard
+        .get(&process_id)
+        .ok_or_else(|| format!("No active process with id: {}", process_id))?
+    {
+        ManagedProcess::Pty { master, .. } => master
+            .resize(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| e.to_string()),
+        ManagedProcess::Simple { .. } => Err("process was not started with a pty".to_string()),
+    }
+}
+
+#[command]
+pub async fn kill_process(
+    process_id: String,
+    app: AppHandle,
+    registry: tauri::State<'_, ProcessRegistry>,
+) -> Result<(), String> {
+    let mut process = registry
+        .0
+        .lock()
+        .map_err(|_| "process registry poisoned".to_string())?
+        .remove(&process_id)
+        .ok_or_else(|| format!("No active process with id: {}", process_id))?;
+
+    let code = match &mut process {
+        ManagedProcess::Simple { child } => {
+            child.kill().map_err(|e| e.to_string())?;
+            child.wait().ok().and_then(|s| s.code())
+        }
+        ManagedProcess::Pty { child, .. } => {
+            child.kill().map_err(|e| e.to_string())?;
+            child
+                .wait()
+                .ok()
+                .map(|s| s.exit_code() as i32)
+        }
+    };
+
+    let _ = app.emit(
+        "process-exit",
+        ProcessExitEvent {
+            process_id,
+            code,
+        },
+    );
+
+    Ok(())
+}
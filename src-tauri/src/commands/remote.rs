@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use russh::client::{self, Msg};
+use russh::{Channel, ChannelStream};
+use russh_keys::key::PublicKey;
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+/// Where pinned SSH host-key fingerprints are persisted, one `host fingerprint` pair
+/// per line — deliberately simple (not OpenSSH `known_hosts` format) since we only
+/// ever look up by exact host string.
+fn known_hosts_path() -> std::path::PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("az-interface")
+        .join("ssh_known_hosts")
+}
+
+fn load_known_hosts() -> HashMap<String, String> {
+    let Ok(contents) = std::fs::read_to_string(known_hosts_path()) else {
+        return HashMap::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(host, fingerprint)| (host.to_string(), fingerprint.to_string()))
+        .collect()
+}
+
+fn pin_known_host(host: &str, fingerprint: &str) -> std::io::Result<()> {
+    let path = known_hosts_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut contents = std::fs::read_to_string(&path).unwrap_or_default();
+    contents.push_str(&format!("{} {}\n", host, fingerprint));
+    std::fs::write(path, contents)
+}
+
+use crate::commands::file_ops::{FileContent, FileEntry};
+
+/// A single request dispatched to a remote agent over `RemoteConnection::transport`.
+/// Mirrors `read_file_content`, `write_file_content`, `list_directory` and
+/// `execute_command` so the remote agent can be a thin relay back to the same
+/// operations running on the target host. The other `file_ops`/`system` commands
+/// have no remote variant and always run locally.
+#[derive(Serialize, Deserialize)]
+pub enum RemoteRequest {
+    ReadFile { path: String },
+    WriteFile { path: String, content: String },
+    ListDirectory { path: String },
+    ExecuteCommand { command: String },
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum RemoteResponse {
+    FileContent(FileContent),
+    Ack,
+    DirectoryListing(Vec<FileEntry>),
+    CommandOutput(String),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConnectionInfo {
+    pub id: String,
+    pub destination: String,
+    pub connected: bool,
+}
+
+/// Credentials for an authenticated `ssh://` destination. `tcp://` (and bare
+/// `host:port`) destinations carry no authentication of their own — they assume the
+/// relay agent on the other end is only reachable on a trusted network.
+#[derive(Serialize, Deserialize)]
+pub struct RemoteCredentials {
+    pub username: String,
+    pub password: Option<String>,
+    pub private_key: Option<String>,
+}
+
+/// The command an `ssh://` destination execs once authenticated; expected to speak
+/// the same newline-delimited JSON protocol as the plain `tcp://` relay agent.
+const REMOTE_AGENT_COMMAND: &str = "az-remote-agent";
+
+/// Wraps an SSH channel together with the client session that owns it — the session
+/// must stay alive for as long as the channel is used.
+struct SshTransport {
+    _session: client::Handle<PinningHostKeyVerifier>,
+    stream: ChannelStream<Msg>,
+}
+
+impl AsyncRead for SshTransport {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SshTransport {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.stream).poll_shutdown(cx)
+    }
+}
+
+/// `russh` host-key handler implementing trust-on-first-use: the first connection to
+/// a given `host` pins its key fingerprint to disk, and every later connection to
+/// that same host is rejected if the fingerprint no longer matches. This is what
+/// makes `authenticate_password` safe to use here — without it, a MITM presenting
+/// any key at all would be accepted and would simply harvest the plaintext password.
+struct PinningHostKeyVerifier {
+    host: String,
+}
+
+impl client::Handler for PinningHostKeyVerifier {
+    type Error = russh::Error;
+
+    async fn check_server_key(&mut self, server_public_key: &PublicKey) -> Result<bool, Self::Error> {
+        let fingerprint = server_public_key.fingerprint();
+        let known_hosts = load_known_hosts();
+
+        match known_hosts.get(&self.host) {
+            Some(pinned) if pinned == &fingerprint => Ok(true),
+            Some(_) => {
+                tracing::error!(host = %self.host, "SSH host key fingerprint changed, refusing connection");
+                Ok(false)
+            }
+            None => {
+                if let Err(e) = pin_known_host(&self.host, &fingerprint) {
+                    tracing::error!(host = %self.host, error = %e, "failed to persist SSH host key pin");
+                    return Ok(false);
+                }
+                tracing::warn!(host = %self.host, %fingerprint, "pinning new SSH host key (trust on first use)");
+                Ok(true)
+            }
+        }
+    }
+}
+
+async fn connect_ssh(host: &str, credentials: &RemoteCredentials) -> Result<SshTransport, String> {
+    let config = Arc::new(client::Config::default());
+    let mut session = client::connect(
+        config,
+        host,
+        PinningHostKeyVerifier {
+            host: host.to_string(),
+        },
+    )
+    .await
+    .map_err(|e| format!("SSH connection to {} failed: {}", host, e))?;
+
+    let authenticated = if let Some(key_pem) = &credentials.private_key {
+        let key_pair = russh_keys::decode_secret_key(key_pem, None).map_err(|e| e.to_string())?;
+        session
+            .authenticate_publickey(&credentials.username, Arc::new(key_pair))
+            .await
+            .map_err(|e| e.to_string())?
+    } else if let Some(password) = &credentials.password {
+        session
+            .authenticate_password(&credentials.username, password)
+            .await
+            .map_err(|e| e.to_string())?
+    } else {
+        return Err("ssh:// credentials must include a password or private_key".to_string());
+    };
+
+    if !authenticated {
+        return Err(format!("SSH authentication to {} was rejected", host));
+    }
+
+    let channel: Channel<Msg> = session.channel_open_session().await.map_err(|e| e.to_string())?;
+    channel
+        .exec(true, REMOTE_AGENT_COMMAND)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(SshTransport {
+        _session: session,
+        stream: channel.into_stream(),
+    })
+}
+
+type BoxedTransport = Box<dyn AsyncReadWrite>;
+
+trait AsyncReadWrite: AsyncRead + AsyncWrite + Send + Unpin {}
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> AsyncReadWrite for T {}
+
+pub struct RemoteConnection {
+    pub destination: String,
+    transport: AsyncMutex<BoxedTransport>,
+}
+
+#[derive(Default)]
+pub struct ConnectionRegistry(Mutex<HashMap<String, Arc<RemoteConnection>>>);
+
+impl ConnectionRegistry {
+    /// Looks up an open connection and round-trips a single request/response pair
+    /// over its transport as newline-delimited JSON.
+    pub async fn dispatch(
+        &self,
+        connection_id: &str,
+        request: RemoteRequest,
+    ) -> Result<RemoteResponse, String> {
+        let connection = {
+            let connections = self
+                .0
+                .lock()
+                .map_err(|_| "connection registry poisoned".to_string())?;
+            connections
+                .get(connection_id)
+                .cloned()
+                .ok_or_else(|| format!("No active connection with id: {}", connection_id))?
+        };
+
+        let mut stream = connection.transport.lock().await;
+
+        let mut payload = serde_json::to_vec(&request).map_err(|e| e.to_string())?;
+        payload.push(b'\n');
+        stream.write_all(&payload).await.map_err(|e| e.to_string())?;
+
+        let mut buf = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            let n = stream.read(&mut byte).await.map_err(|e| e.to_string())?;
+            if n == 0 || byte[0] == b'\n' {
+                break;
+            }
+            buf.push(byte[0]);
+        }
+
+        serde_json::from_slice(&buf).map_err(|e| e.to_string())
+    }
+}
+
+#[command]
+pub async fn connect_host(
+    destination: String,
+    credentials: Option<RemoteCredentials>,
+    registry: tauri::State<'_, ConnectionRegistry>,
+) -> Result<String, String> {
+    let transport: BoxedTransport = if let Some(host) = destination.strip_prefix("ssh://") {
+        let credentials = credentials.ok_or("ssh:// destinations require credentials")?;
+        Box::new(connect_ssh(host, &credentials).await?)
+    } else {
+        // `tcp://host:port`, or a bare `host:port` for backwards compatibility.
+        let host = destination.strip_prefix("tcp://").unwrap_or(&destination);
+        Box::new(
+            TcpStream::connect(host)
+                .await
+                .map_err(|e| format!("Failed to connect to {}: {}", host, e))?,
+        )
+    };
+
+    let id = Uuid::new_v4().to_string();
+    registry
+        .0
+        .lock()
+        .map_err(|_| "connection registry poisoned".to_string())?
+        .insert(
+            id.clone(),
+            Arc::new(RemoteConnection {
+                destination,
+                transport: AsyncMutex::new(transport),
+            }),
+        );
+
+    Ok(id)
+}
+
+#[command]
+pub async fn disconnect_host(
+    connection_id: String,
+    registry: tauri::State<'_, ConnectionRegistry>,
+) -> Result<(), String> {
+    registry
+        .0
+        .lock()
+        .map_err(|_| "connection registry poisoned".to_string())?
+        .remove(&connection_id)
+        .ok_or_else(|| format!("No active connection with id: {}", connection_id))?;
+
+    Ok(())
+}
+
+#[command]
+pub async fn list_connections(
+    registry: tauri::State<'_, ConnectionRegistry>,
+) -> Result<Vec<ConnectionInfo>, String> {
+    let connections = registry
+        .0
+        .lock()
+        .map_err(|_| "connection registry poisoned".to_string())?;
+
+    Ok(connections
+        .iter()
+        .map(|(id, conn)| ConnectionInfo {
+            id: id.clone(),
+            destination: conn.destination.clone(),
+            connected: true,
+        })
+        .collect())
+}
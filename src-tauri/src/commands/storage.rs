@@ -0,0 +1,292 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rusty_s3::actions::{DeleteObject, GetObject, HeadObject, ListObjectsV2, PutObject, S3Action};
+use rusty_s3::{Bucket, Credentials, UrlStyle};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::commands::file_ops::FileEntry;
+
+/// How long presigned S3 request URLs remain valid for.
+const PRESIGN_DURATION: Duration = Duration::from_secs(60);
+
+/// Backend-agnostic operations the `file_ops` commands dispatch through, resolved
+/// per call from the `scheme://` prefix on the incoming path (`file://` or bare
+/// paths go to `FileStore`, `s3://bucket/key` goes to `S3Store`).
+#[async_trait]
+pub trait Store: Send + Sync {
+    async fn read(&self, key: &str) -> Result<Vec<u8>, String>;
+    async fn write(&self, key: &str, data: Vec<u8>) -> Result<(), String>;
+    async fn list(&self, prefix: &str) -> Result<Vec<FileEntry>, String>;
+    async fn delete(&self, key: &str) -> Result<(), String>;
+    async fn exists(&self, key: &str) -> Result<bool, String>;
+}
+
+pub struct FileStore;
+
+#[async_trait]
+impl Store for FileStore {
+    async fn read(&self, key: &str) -> Result<Vec<u8>, String> {
+        tokio::fs::read(key).await.map_err(|e| e.to_string())
+    }
+
+    async fn write(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        let path = PathBuf::from(key);
+        if let Some(parent) = path.parent() {
+            if tokio::fs::metadata(parent).await.is_err() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        tokio::fs::write(&path, data).await.map_err(|e| e.to_string())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<FileEntry>, String> {
+        let mut entries = Vec::new();
+        let mut read_dir = tokio::fs::read_dir(prefix).await.map_err(|e| e.to_string())?;
+
+        while let Some(entry) = read_dir.next_entry().await.map_err(|e| e.to_string())? {
+            let path = entry.path();
+            let metadata = entry.metadata().await.map_err(|e| e.to_string())?;
+            entries.push(FileEntry {
+                name: path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                path: path.to_string_lossy().to_string(),
+                is_directory: metadata.is_dir(),
+                size: if metadata.is_dir() { None } else { Some(metadata.len()) },
+                modified: metadata.modified().ok().map(|t| format!("{:?}", t)),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let path = PathBuf::from(key);
+        let metadata = tokio::fs::metadata(&path).await.map_err(|e| e.to_string())?;
+        if metadata.is_dir() {
+            tokio::fs::remove_dir_all(&path).await.map_err(|e| e.to_string())
+        } else {
+            tokio::fs::remove_file(&path).await.map_err(|e| e.to_string())
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, String> {
+        Ok(tokio::fs::metadata(key).await.is_ok())
+    }
+}
+
+/// Endpoint/credentials for the S3-compatible backend, held in the managed
+/// `StorageConfig` state rather than passed per-call. That state starts empty and is
+/// only set by the `configure_s3_store` command; nothing fills it at app setup, so
+/// `s3://` paths fail until that command has been called.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+pub struct S3Store {
+    config: S3Config,
+    client: reqwest::Client,
+}
+
+impl S3Store {
+    pub fn new(config: S3Config) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn bucket(&self, bucket_name: &str) -> Result<Bucket, String> {
+        let endpoint = self
+            .config
+            .endpoint
+            .parse()
+            .map_err(|e| format!("invalid S3 endpoint: {}", e))?;
+        Bucket::new(
+            endpoint,
+            UrlStyle::Path,
+            bucket_name.to_string(),
+            self.config.region.clone(),
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    fn credentials(&self) -> Credentials {
+        Credentials::new(&self.config.access_key, &self.config.secret_key)
+    }
+}
+
+fn split_bucket_key(key: &str) -> Result<(&str, &str), String> {
+    key.split_once('/')
+        .ok_or_else(|| format!("s3 path must be bucket/key, got: {}", key))
+}
+
+#[async_trait]
+impl Store for S3Store {
+    async fn read(&self, key: &str) -> Result<Vec<u8>, String> {
+        let (bucket_name, object_key) = split_bucket_key(key)?;
+        let bucket = self.bucket(bucket_name)?;
+        let credentials = self.credentials();
+        let url = GetObject::new(&bucket, Some(&credentials), object_key).sign(PRESIGN_DURATION);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        response.bytes().await.map(|b| b.to_vec()).map_err(|e| e.to_string())
+    }
+
+    async fn write(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        let (bucket_name, object_key) = split_bucket_key(key)?;
+        let bucket = self.bucket(bucket_name)?;
+        let credentials = self.credentials();
+        let url = PutObject::new(&bucket, Some(&credentials), object_key).sign(PRESIGN_DURATION);
+
+        self.client
+            .put(url)
+            .body(data)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<FileEntry>, String> {
+        let (bucket_name, object_prefix) = split_bucket_key(prefix)?;
+        let bucket = self.bucket(bucket_name)?;
+        let credentials = self.credentials();
+
+        let mut entries = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        // `ListObjectsV2` caps each response at 1000 keys; keep following
+        // `NextContinuationToken` until the listing reports it's no longer truncated.
+        loop {
+            let mut action = ListObjectsV2::new(&bucket, Some(&credentials));
+            action.with_prefix(object_prefix);
+            if let Some(token) = &continuation_token {
+                action.with_continuation_token(token);
+            }
+            let url = action.sign(PRESIGN_DURATION);
+
+            let body = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .text()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let listing = ListObjectsV2::parse_response(&body).map_err(|e| e.to_string())?;
+
+            entries.extend(listing.contents.into_iter().map(|object| FileEntry {
+                name: object
+                    .key
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&object.key)
+                    .to_string(),
+                path: format!("s3://{}/{}", bucket_name, object.key),
+                is_directory: false,
+                size: Some(object.size),
+                modified: Some(object.last_modified),
+            }));
+
+            match listing.next_continuation_token {
+                Some(token) if listing.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let (bucket_name, object_key) = split_bucket_key(key)?;
+        let bucket = self.bucket(bucket_name)?;
+        let credentials = self.credentials();
+        let url = DeleteObject::new(&bucket, Some(&credentials), object_key).sign(PRESIGN_DURATION);
+
+        self.client
+            .delete(url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, String> {
+        let (bucket_name, object_key) = split_bucket_key(key)?;
+        let bucket = self.bucket(bucket_name)?;
+        let credentials = self.credentials();
+        let url = HeadObject::new(&bucket, Some(&credentials), object_key).sign(PRESIGN_DURATION);
+
+        let response = self.client.head(url).send().await.map_err(|e| e.to_string())?;
+        Ok(response.status().is_success())
+    }
+}
+
+#[derive(Default)]
+pub struct StorageConfig(Mutex<Option<S3Config>>);
+
+impl StorageConfig {
+    pub fn set(&self, config: S3Config) -> Result<(), String> {
+        *self.0.lock().map_err(|_| "storage config poisoned".to_string())? = Some(config);
+        Ok(())
+    }
+
+    fn s3_config(&self) -> Result<S3Config, String> {
+        self.0
+            .lock()
+            .map_err(|_| "storage config poisoned".to_string())?
+            .clone()
+            .ok_or_else(|| "S3 storage is not configured".to_string())
+    }
+}
+
+#[command]
+pub async fn configure_s3_store(
+    config: S3Config,
+    storage: tauri::State<'_, StorageConfig>,
+) -> Result<(), String> {
+    storage.set(config)
+}
+
+/// Splits a `scheme://rest` path into its backend and the key to hand that backend.
+/// A path with no recognized scheme is treated as a plain local filesystem path.
+pub fn resolve_store(path: &str, config: &StorageConfig) -> Result<(Box<dyn Store>, String), String> {
+    if let Some(key) = path.strip_prefix("s3://") {
+        let store = S3Store::new(config.s3_config()?);
+        return Ok((Box::new(store), key.to_string()));
+    }
+
+    if let Some(key) = path.strip_prefix("file://") {
+        return Ok((Box::new(FileStore), key.to_string()));
+    }
+
+    Ok((Box::new(FileStore), path.to_string()))
+}
@@ -2,6 +2,8 @@ use tauri::command;
 use std::process::Command;
 use serde::{Deserialize, Serialize};
 
+use crate::commands::remote::{ConnectionRegistry, RemoteRequest, RemoteResponse};
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
@@ -21,7 +23,22 @@ pub struct SystemStatus {
 }
 
 #[command]
-pub async fn execute_command(command: String) -> Result<String, String> {
+pub async fn execute_command(
+    command: String,
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+) -> Result<String, String> {
+    if let Some(connection_id) = connection {
+        return match connections
+            .dispatch(&connection_id, RemoteRequest::ExecuteCommand { command })
+            .await?
+        {
+            RemoteResponse::CommandOutput(output) => Ok(output),
+            RemoteResponse::Error(e) => Err(e),
+            _ => Err("Unexpected response from remote agent".to_string()),
+        };
+    }
+
     let output = if cfg!(target_os = "windows") {
         Command::new("cmd")
             .args(["/C", &command])
@@ -84,57 +101,16 @@ pub async fn get_system_status() -> Result<SystemStatus, String> {
 }
 
 #[command]
-pub async fn manage_docker_containers(action: String, container_name: Option<String>) -> Result<String, String> {
-    let mut docker_cmd = Command::new("docker");
-    
-    match action.as_str() {
-        "ps" => {
-            docker_cmd.arg("ps");
-        }
-        "start" => {
-            if let Some(name) = container_name {
-                docker_cmd.args(["start", &name]);
-            } else {
-                return Err("Container name required for start action".to_string());
-            }
-        }
-        "stop" => {
-            if let Some(name) = container_name {
-                docker_cmd.args(["stop", &name]);
-            } else {
-                return Err("Container name required for stop action".to_string());
-            }
-        }
-        "logs" => {
-            if let Some(name) = container_name {
-                docker_cmd.args(["logs", &name]);
-            } else {
-                return Err("Container name required for logs action".to_string());
-            }
-        }
-        _ => {
-            return Err(format!("Unknown Docker action: {}", action));
-        }
-    }
-    
-    let output = docker_cmd.output().map_err(|e| e.to_string())?;
-    let result = String::from_utf8_lossy(&output.stdout);
-    
-    if !output.stderr.is_empty() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Err(error.to_string())
-    } else {
-        Ok(result.to_string())
-    }
-}
-
-#[command]
-pub async fn restart_service(service_name: String) -> Result<String, String> {
+pub async fn restart_service(
+    service_name: String,
+    connection: Option<String>,
+    connections: tauri::State<'_, ConnectionRegistry>,
+) -> Result<String, String> {
     let command = if cfg!(target_os = "windows") {
         format!("net stop {} && net start {}", service_name, service_name)
     } else {
         format!("sudo systemctl restart {}", service_name)
     };
-    
-    execute_command(command).await
+
+    execute_command(command, connection, connections).await
 }
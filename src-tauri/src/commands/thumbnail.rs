@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use image::imageops::FilterType;
+use image::{GenericImageView, ImageFormat};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+const BASE83_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+#[derive(Serialize, Deserialize)]
+pub struct ThumbnailResult {
+    pub data: Vec<u8>,
+    pub mime_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Default)]
+pub struct ThumbnailCache {
+    thumbnails: Mutex<HashMap<String, (u64, Vec<u8>)>>,
+    blurhashes: Mutex<HashMap<String, (u64, String)>>,
+}
+
+fn file_mtime(path: &std::path::Path) -> Result<u64, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    let modified = metadata.modified().map_err(|e| e.to_string())?;
+    Ok(modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs())
+}
+
+fn cache_key(path: &str, mtime: u64) -> String {
+    format!("{}:{}", path, mtime)
+}
+
+#[command]
+pub async fn get_thumbnail(
+    path: String,
+    max_edge: u32,
+    cache: tauri::State<'_, ThumbnailCache>,
+) -> Result<ThumbnailResult, String> {
+    let file_path = PathBuf::from(&path);
+    let mtime = tokio::task::spawn_blocking({
+        let file_path = file_path.clone();
+        move || file_mtime(&file_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    let key = cache_key(&path, mtime);
+
+    if let Some((_, cached)) = cache
+        .thumbnails
+        .lock()
+        .map_err(|_| "thumbnail cache poisoned".to_string())?
+        .get(&key)
+        .cloned()
+    {
+        let (width, height) = tokio::task::spawn_blocking({
+            let cached = cached.clone();
+            move || -> Result<(u32, u32), String> {
+                let img = image::load_from_memory(&cached).map_err(|e| e.to_string())?;
+                Ok((img.width(), img.height()))
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+
+        return Ok(ThumbnailResult {
+            width,
+            height,
+            data: cached,
+            mime_type: "image/png".to_string(),
+        });
+    }
+
+    let (data, width, height) = tokio::task::spawn_blocking(move || -> Result<(Vec<u8>, u32, u32), String> {
+        let img = image::open(&file_path).map_err(|e| e.to_string())?;
+        let thumbnail = img.resize(max_edge, max_edge, FilterType::Lanczos3);
+
+        let mut buf = std::io::Cursor::new(Vec::new());
+        thumbnail
+            .write_to(&mut buf, ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+
+        Ok((buf.into_inner(), thumbnail.width(), thumbnail.height()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    cache
+        .thumbnails
+        .lock()
+        .map_err(|_| "thumbnail cache poisoned".to_string())?
+        .insert(key, (mtime, data.clone()));
+
+    Ok(ThumbnailResult {
+        width,
+        height,
+        data,
+        mime_type: "image/png".to_string(),
+    })
+}
+
+#[command]
+pub async fn get_blurhash(
+    path: String,
+    cache: tauri::State<'_, ThumbnailCache>,
+) -> Result<String, String> {
+    let file_path = PathBuf::from(&path);
+    let mtime = tokio::task::spawn_blocking({
+        let file_path = file_path.clone();
+        move || file_mtime(&file_path)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    let key = cache_key(&path, mtime);
+
+    if let Some((_, cached)) = cache
+        .blurhashes
+        .lock()
+        .map_err(|_| "blurhash cache poisoned".to_string())?
+        .get(&key)
+        .cloned()
+    {
+        return Ok(cached);
+    }
+
+    let hash = tokio::task::spawn_blocking(move || -> Result<String, String> {
+        let img = image::open(&file_path).map_err(|e| e.to_string())?.to_rgb8();
+        Ok(encode_blurhash(&img, 4, 3))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    cache
+        .blurhashes
+        .lock()
+        .map_err(|_| "blurhash cache poisoned".to_string())?
+        .insert(key, (mtime, hash.clone()));
+
+    Ok(hash)
+}
+
+/// Implements the standard blurhash encoding: a DC term plus `components_x * components_y - 1`
+/// AC terms, each a 2D DCT-like basis-weighted average over the image in linear RGB.
+fn encode_blurhash(img: &image::RgbImage, components_x: u32, components_y: u32) -> String {
+    let (width, height) = img.dimensions();
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+
+    for j in 0..components_y {
+        for i in 0..components_x {
+            let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+            let mut r = 0.0f64;
+            let mut g = 0.0f64;
+            let mut b = 0.0f64;
+
+            for y in 0..height {
+                for x in 0..width {
+                    let basis = normalisation
+                        * (std::f64::consts::PI * i as f64 * x as f64 / width as f64).cos()
+                        * (std::f64::consts::PI * j as f64 * y as f64 / height as f64).cos();
+
+                    let pixel = img.get_pixel(x, y);
+                    r += basis * srgb_to_linear(pixel[0]);
+                    g += basis * srgb_to_linear(pixel[1]);
+                    b += basis * srgb_to_linear(pixel[2]);
+                }
+            }
+
+            let scale = 1.0 / (width as f64 * height as f64);
+            factors.push((r * scale, g * scale, b * scale));
+        }
+    }
+
+    serialize_blurhash(components_x, components_y, &factors)
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (srgb * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn serialize_blurhash(components_x: u32, components_y: u32, factors: &[(f64, f64, f64)]) -> String {
+    let size_flag = (components_x - 1) + (components_y - 1) * 9;
+    let mut result = base83_encode(size_flag as u32, 1);
+
+    let dc = factors[0];
+    let ac = &factors[1..];
+
+    let max_ac = ac
+        .iter()
+        .flat_map(|(r, g, b)| [r.abs(), g.abs(), b.abs()])
+        .fold(0.0f64, f64::max);
+
+    let quantised_max_ac = if !ac.is_empty() {
+        ((max_ac * 166.0 - 0.5).clamp(0.0, 82.0)) as u32
+    } else {
+        0
+    };
+
+    result += &base83_encode(quantised_max_ac, 1);
+    result += &base83_encode(encode_dc(dc), 4);
+
+    let actual_max_ac = if !ac.is_empty() {
+        (quantised_max_ac as f64 + 1.0) / 166.0
+    } else {
+        1.0
+    };
+
+    for &(r, g, b) in ac {
+        result += &base83_encode(encode_ac(r, g, b, actual_max_ac), 2);
+    }
+
+    result
+}
+
+fn encode_dc(rgb: (f64, f64, f64)) -> u32 {
+    let r = linear_to_srgb(rgb.0) as u32;
+    let g = linear_to_srgb(rgb.1) as u32;
+    let b = linear_to_srgb(rgb.2) as u32;
+    (r << 16) + (g << 8) + b
+}
+
+fn encode_ac(r: f64, g: f64, b: f64, max_value: f64) -> u32 {
+    let quantise = |value: f64| -> u32 {
+        let scaled = value / max_value;
+        ((scaled.signum() * scaled.abs().sqrt() * 9.0 + 9.5).clamp(0.0, 18.0)) as u32
+    };
+
+    let qr = quantise(r);
+    let qg = quantise(g);
+    let qb = quantise(b);
+
+    qr * 19 * 19 + qg * 19 + qb
+}
+
+fn base83_encode(value: u32, length: usize) -> String {
+    let mut result = vec![0u8; length];
+    let mut value = value;
+    for i in (0..length).rev() {
+        let digit = (value % 83) as usize;
+        result[i] = BASE83_ALPHABET[digit];
+        value /= 83;
+    }
+    String::from_utf8(result).expect("base83 alphabet is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base83_decode(digits: &str) -> u32 {
+        digits.chars().fold(0, |acc, c| {
+            let digit = BASE83_ALPHABET.iter().position(|&b| b == c as u8).unwrap();
+            acc * 83 + digit as u32
+        })
+    }
+
+    #[test]
+    fn base83_encode_roundtrips_through_decode() {
+        for value in [0u32, 1, 82, 83, 1000, 82 * 83 + 82] {
+            let encoded = base83_encode(value, 4);
+            assert_eq!(base83_decode(&encoded), value);
+        }
+    }
+
+    #[test]
+    fn base83_encode_pads_to_requested_length() {
+        assert_eq!(base83_encode(0, 1).len(), 1);
+        assert_eq!(base83_encode(0, 4).len(), 4);
+    }
+
+    #[test]
+    fn encode_ac_clamps_into_19x19x19_range() {
+        let max_value = 1.0;
+        for &(r, g, b) in &[(-1.0, -1.0, -1.0), (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), (5.0, 5.0, 5.0)] {
+            let encoded = encode_ac(r, g, b, max_value);
+            assert!(encoded < 19 * 19 * 19);
+        }
+    }
+
+    #[test]
+    fn srgb_linear_roundtrip_is_close_to_identity() {
+        for value in [0u8, 1, 64, 128, 200, 255] {
+            let roundtripped = linear_to_srgb(srgb_to_linear(value));
+            assert!((roundtripped as i16 - value as i16).abs() <= 1);
+        }
+    }
+}
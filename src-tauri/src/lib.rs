@@ -10,7 +10,6 @@ pub fn run() {
       execute_command,
       list_processes,
       get_system_status,
-      manage_docker_containers,
       restart_service,
       
       // File operations
@@ -21,15 +20,50 @@ pub fn run() {
       delete_file,
       file_exists,
       get_file_info,
-      
+
+      // File watching
+      watch_path,
+      unwatch_path,
+
+      // Process management
+      spawn_process,
+      write_process_stdin,
+      resize_pty,
+      kill_process,
+
+      // Remote host connections
+      connect_host,
+      disconnect_host,
+      list_connections,
+
+      // Image thumbnails
+      get_thumbnail,
+      get_blurhash,
+
+      // Storage backend configuration
+      configure_s3_store,
+
+      // Docker / compose orchestration
+      compose_up,
+      compose_down,
+      compose_ps,
+      restart_container,
+      stream_container_logs,
+
       // AI integration
       call_gemini_api,
+      call_ai_stream,
       generate_image,
       analyze_system_state,
       get_ai_models,
       save_api_key,
       test_api_connection,
     ])
+    .manage(commands::file_watch::WatcherRegistry::default())
+    .manage(commands::process::ProcessRegistry::default())
+    .manage(commands::remote::ConnectionRegistry::default())
+    .manage(commands::thumbnail::ThumbnailCache::default())
+    .manage(commands::storage::StorageConfig::default())
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(